@@ -1,10 +1,22 @@
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use colored::*;
+use crossbeam_channel::{Receiver, Sender};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
-use walkdir::WalkDir;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often (in directories scanned) a worker emits a progress update.
+const PROGRESS_INTERVAL: usize = 64;
+
+/// Maximum number of symlinks that may be followed along a single branch before
+/// descent is aborted, guarding against symlink cycles.
+const MAX_NUMBER_OF_SYMLINK_JUMPS: usize = 20;
 
 /// High-performance CLI tool to recursively clean .DS_Store junk files
 ///
@@ -36,12 +48,346 @@ struct Args {
     /// Skip hidden directories (directories starting with ., but not .DS_Store files)
     #[arg(long)]
     skip_hidden: bool,
+
+    /// Number of worker threads used for parallel scanning (0 means available parallelism)
+    #[arg(short = 'j', long, default_value = "0")]
+    jobs: usize,
+
+    /// Show a live progress line while scanning
+    #[arg(long)]
+    progress: bool,
+
+    /// Junk-file targets to clean (repeatable or comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "ds-store")]
+    targets: Vec<Target>,
+
+    /// Additional custom glob(s) to treat as junk, matched against the file name (repeatable)
+    #[arg(long = "pattern")]
+    patterns: Vec<String>,
+
+    /// Glob(s) of paths or subtrees to exclude from scanning (repeatable)
+    #[arg(long = "exclude")]
+    exclude: Vec<String>,
+
+    /// Glob(s) restricting cleaning to matching paths (repeatable)
+    #[arg(long = "include")]
+    include: Vec<String>,
+
+    /// Follow symlinked directories (off by default to avoid cycles)
+    #[arg(long = "follow-symlinks")]
+    follow_symlinks: bool,
+
+    /// Output format (human, json, or ndjson)
+    #[arg(long, value_enum, default_value = "human")]
+    format: Format,
+}
+
+/// How results are rendered to stdout.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Colored, human-readable output (the default)
+    Human,
+    /// A single JSON document: an array of events plus a summary object
+    Json,
+    /// Newline-delimited JSON: one event per line, summary last
+    Ndjson,
+}
+
+/// A named family of OS-generated junk files.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Target {
+    /// macOS Finder metadata (`.DS_Store`)
+    DsStore,
+    /// macOS AppleDouble resource forks (`._*`)
+    AppleDouble,
+    /// Windows thumbnail cache (`Thumbs.db`)
+    ThumbsDb,
+    /// Windows folder settings (`Desktop.ini`)
+    DesktopIni,
+    /// macOS Spotlight index (`.Spotlight-V100`)
+    SpotlightV100,
+    /// macOS volume trash (`.Trashes`)
+    Trashes,
+}
+
+impl Target {
+    /// Whether the given file name belongs to this target family.
+    fn matches(self, name: &str) -> bool {
+        match self {
+            Target::DsStore => name == ".DS_Store",
+            Target::AppleDouble => name.starts_with("._"),
+            Target::ThumbsDb => name == "Thumbs.db",
+            Target::DesktopIni => name == "Desktop.ini",
+            Target::SpotlightV100 => name == ".Spotlight-V100",
+            Target::Trashes => name == ".Trashes",
+        }
+    }
+}
+
+/// Matcher deciding whether a file counts as junk.
+///
+/// Built once in `main` from the enabled `--targets` and `--pattern` globs and
+/// consulted both while scanning and again inside `move_to_trash`'s safety
+/// recheck, so a file is only ever trashed when it matches one of the
+/// explicitly enabled patterns.
+struct JunkMatcher {
+    targets: Vec<Target>,
+    globs: Vec<glob::Pattern>,
+}
+
+impl JunkMatcher {
+    fn new(targets: &[Target], patterns: &[String]) -> Result<Self> {
+        let globs = patterns
+            .iter()
+            .map(|p| {
+                glob::Pattern::new(p).with_context(|| format!("Invalid glob pattern: {}", p))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            targets: targets.to_vec(),
+            globs,
+        })
+    }
+
+    /// Whether `path`'s file name matches an enabled target or custom glob.
+    fn matches(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        self.targets.iter().any(|t| t.matches(name))
+            || self.globs.iter().any(|g| g.matches(name))
+    }
+}
+
+/// Compiled set of path globs controlling which subtrees and files the scan
+/// touches, inspired by czkawka's `ExcludedItems` and Mercurial's matcher
+/// functions. Patterns are matched against the full (canonicalized) path.
+///
+/// Excluded directories are pruned before descent, so the whole subtree is
+/// skipped rather than filtered file-by-file. An `include` set, when non-empty,
+/// restricts which files are acted upon without pruning directories (a
+/// non-matching directory may still contain matching descendants).
+struct ExcludedItems {
+    excluded: Vec<glob::Pattern>,
+    included: Vec<glob::Pattern>,
+}
+
+impl ExcludedItems {
+    fn new(exclude: &[String], include: &[String]) -> Result<Self> {
+        let compile = |patterns: &[String]| -> Result<Vec<glob::Pattern>> {
+            patterns
+                .iter()
+                .map(|p| {
+                    glob::Pattern::new(p).with_context(|| format!("Invalid glob pattern: {}", p))
+                })
+                .collect()
+        };
+        Ok(Self {
+            excluded: compile(exclude)?,
+            included: compile(include)?,
+        })
+    }
+
+    /// Whether `path` is excluded and any subtree rooted at it should be pruned.
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.excluded.iter().any(|g| g.matches_path(path))
+    }
+
+    /// Whether `path` falls within the include set (trivially true when no
+    /// include patterns were supplied).
+    fn is_included(&self, path: &Path) -> bool {
+        self.included.is_empty() || self.included.iter().any(|g| g.matches_path(path))
+    }
+
+    /// Whether a matched junk file at `path` is eligible for cleaning.
+    fn allows_file(&self, path: &Path) -> bool {
+        !self.is_excluded(path) && self.is_included(path)
+    }
+}
+
+/// A single structured event describing what happened to one file.
+#[derive(Serialize)]
+struct Record {
+    action: &'static str,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The final summary object, mirroring `CleanStats`.
+#[derive(Serialize)]
+struct Summary {
+    found: usize,
+    moved: usize,
+    failed: usize,
+    unreadable: usize,
+}
+
+/// Output abstraction so human and machine-readable renderers stay in sync.
+///
+/// Every user-facing result flows through here: in `Human` mode colored lines
+/// are printed (each moved/failed line names its file), while the `Json`/`Ndjson` modes emit
+/// one record per found/moved/failed file and a final summary object. Warnings
+/// always go to stderr so stdout stays pure structured data in the JSON modes.
+struct Output {
+    format: Format,
+    dry_run: bool,
+    verbose: bool,
+    /// Buffered events, only used in `Json` mode.
+    buffer: Mutex<Vec<Record>>,
+}
+
+impl Output {
+    fn new(format: Format, dry_run: bool, verbose: bool) -> Self {
+        Self {
+            format,
+            dry_run,
+            verbose,
+            buffer: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn is_human(&self) -> bool {
+        matches!(self.format, Format::Human)
+    }
+
+    /// Route one structured event to the active machine-readable sink.
+    fn emit(&self, record: Record) {
+        match self.format {
+            Format::Human => {}
+            Format::Ndjson => println!("{}", serde_json::to_string(&record).unwrap()),
+            Format::Json => self.buffer.lock().unwrap().push(record),
+        }
+    }
+
+    /// A `.DS_Store` (or other target) was found. In preview mode this is the
+    /// only event emitted for the file.
+    fn found(&self, path: &Path) {
+        if self.is_human() {
+            if self.dry_run {
+                println!("{} {}", "[Preview]".bright_yellow(), path.display());
+            } else if self.verbose {
+                println!("{} {}", "[Found]".bright_blue(), path.display());
+            }
+            return;
+        }
+        self.emit(Record {
+            action: if self.dry_run { "preview" } else { "found" },
+            path: path.display().to_string(),
+            error: None,
+        });
+    }
+
+    fn moved(&self, path: &Path) {
+        if self.is_human() {
+            if self.verbose {
+                // Include the path so the line is attributable even when rayon
+                // workers interleave output under `--jobs > 1`.
+                println!(
+                    "  {} {}{}",
+                    "✓".green().bold(),
+                    "Moved to trash: ".green(),
+                    path.display().to_string().green()
+                );
+            }
+            return;
+        }
+        self.emit(Record {
+            action: "moved",
+            path: path.display().to_string(),
+            error: None,
+        });
+    }
+
+    fn failed(&self, path: &Path, err: &anyhow::Error) {
+        if self.is_human() {
+            eprintln!(
+                "  {} Failed to move file {}: {}",
+                "✗".red().bold(),
+                path.display(),
+                err.to_string().red()
+            );
+            return;
+        }
+        self.emit(Record {
+            action: "failed",
+            path: path.display().to_string(),
+            error: Some(err.to_string()),
+        });
+    }
+
+    /// Diagnostics always go to stderr, regardless of format.
+    fn warn(&self, msg: impl std::fmt::Display) {
+        eprintln!("{} {}", "Warning:".yellow(), msg);
+    }
+
+    /// Render the final summary once scanning has finished.
+    fn summary(&self, stats: &CleanStats) {
+        let summary = Summary {
+            found: stats.get_found(),
+            moved: stats.get_moved(),
+            failed: stats.get_failed(),
+            unreadable: stats.get_unreadable(),
+        };
+
+        match self.format {
+            Format::Human => self.human_summary(stats),
+            Format::Ndjson => {
+                println!("{}", serde_json::to_string(&summary).unwrap());
+            }
+            Format::Json => {
+                let buffer = self.buffer.lock().unwrap();
+                let document = serde_json::json!({
+                    "events": &*buffer,
+                    "summary": summary,
+                });
+                println!("{}", serde_json::to_string(&document).unwrap());
+            }
+        }
+    }
+
+    /// The colored, human-readable statistics block.
+    fn human_summary(&self, stats: &CleanStats) {
+        println!();
+        println!("{}", "=".repeat(50).bright_black());
+        println!("{}", "Cleanup Statistics:".bold().cyan());
+        println!(
+            "  {} {}",
+            "Found .DS_Store files:".bold(),
+            stats.get_found().to_string().yellow()
+        );
+
+        if !self.dry_run {
+            println!(
+                "  {} {}",
+                "Successfully moved to trash:".bold(),
+                stats.get_moved().to_string().green()
+            );
+            if stats.get_failed() > 0 {
+                println!(
+                    "  {} {}",
+                    "Failed:".bold(),
+                    stats.get_failed().to_string().red()
+                );
+            }
+        }
+
+        if stats.get_unreadable() > 0 {
+            println!(
+                "  {} {}",
+                "Unreadable directories (skipped):".bold(),
+                stats.get_unreadable().to_string().red()
+            );
+        }
+        println!("{}", "=".repeat(50).bright_black());
+    }
 }
 
 struct CleanStats {
     found: Arc<AtomicUsize>,
     moved: Arc<AtomicUsize>,
     failed: Arc<AtomicUsize>,
+    unreadable: Arc<AtomicUsize>,
 }
 
 impl CleanStats {
@@ -50,6 +396,7 @@ impl CleanStats {
             found: Arc::new(AtomicUsize::new(0)),
             moved: Arc::new(AtomicUsize::new(0)),
             failed: Arc::new(AtomicUsize::new(0)),
+            unreadable: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -65,6 +412,10 @@ impl CleanStats {
         self.failed.fetch_add(1, Ordering::Relaxed);
     }
 
+    fn increment_unreadable(&self) {
+        self.unreadable.fetch_add(1, Ordering::Relaxed);
+    }
+
     fn get_found(&self) -> usize {
         self.found.load(Ordering::Relaxed)
     }
@@ -76,87 +427,188 @@ impl CleanStats {
     fn get_failed(&self) -> usize {
         self.failed.load(Ordering::Relaxed)
     }
+
+    fn get_unreadable(&self) -> usize {
+        self.unreadable.load(Ordering::Relaxed)
+    }
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
+/// A snapshot of scan progress sent from the worker threads to the background
+/// reporter, modeled on the periodic progress updates used by parallel file
+/// scanners such as czkawka.
+struct ProgressData {
+    current_stage: String,
+    entries_checked: usize,
+    entries_to_check: usize,
+    found: usize,
+}
 
-    // Validate input path
-    let scan_path = args
-        .path
-        .canonicalize()
-        .context("Cannot access the specified path")?;
+/// Sender side of the progress subsystem, shared across worker threads.
+///
+/// When progress reporting is disabled the `sender` is `None` and every method
+/// is a cheap no-op, so the hot path stays clean.
+struct Progress {
+    sender: Option<Sender<ProgressData>>,
+    checked: AtomicUsize,
+    to_check: AtomicUsize,
+}
 
-    if !scan_path.exists() {
-        anyhow::bail!("Path does not exist: {}", scan_path.display());
+impl Progress {
+    fn disabled() -> Self {
+        Self {
+            sender: None,
+            checked: AtomicUsize::new(0),
+            to_check: AtomicUsize::new(0),
+        }
     }
 
-    if !scan_path.is_dir() {
-        anyhow::bail!("Path is not a directory: {}", scan_path.display());
+    fn enabled(sender: Sender<ProgressData>) -> Self {
+        Self {
+            sender: Some(sender),
+            checked: AtomicUsize::new(0),
+            to_check: AtomicUsize::new(0),
+        }
     }
 
-    // Display scan information
-    println!(
-        "{} {}",
-        "Scan path:".bold().cyan(),
-        scan_path.display().to_string().yellow()
-    );
-
-    if args.dry_run {
-        println!(
-            "{}",
-            "Mode: Preview mode (files will not be removed)"
-                .bold()
-                .yellow()
-        );
-    } else {
-        println!(
-            "{}",
-            "Mode: Execution mode (files will be moved to trash)"
-                .bold()
-                .green()
-        );
+    /// Add newly discovered directories to the running total still to be
+    /// scanned. Kept cumulative (not per-round) so the rendered
+    /// `checked/to_check` fraction stays meaningful: a directory is always
+    /// counted here before it is ticked as checked.
+    fn add_to_check(&self, n: usize) {
+        if self.sender.is_none() {
+            return;
+        }
+        self.to_check.fetch_add(n, Ordering::Relaxed);
     }
 
-    if args.no_recursive {
-        println!("{}", "Recursion: Disabled".bold());
-    } else if args.max_depth > 0 {
-        println!("{} {}", "Max depth:".bold(), args.max_depth);
+    /// Note that one more directory has been scanned and, every
+    /// `PROGRESS_INTERVAL` directories, push a snapshot to the reporter.
+    fn tick(&self, current: &Path, found: usize) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        let checked = self.checked.fetch_add(1, Ordering::Relaxed) + 1;
+        if checked % PROGRESS_INTERVAL == 0 {
+            let _ = sender.send(ProgressData {
+                current_stage: current.display().to_string(),
+                entries_checked: checked,
+                entries_to_check: self.to_check.load(Ordering::Relaxed),
+                found,
+            });
+        }
     }
+}
 
-    println!();
+/// Spawn the background reporter thread. It renders the most recent update to
+/// stderr, throttled to roughly every 100ms so a fast scan does not flood the
+/// terminal, and clears the status line once the channel closes.
+fn spawn_reporter(rx: Receiver<ProgressData>) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut last_draw: Option<Instant> = None;
+        while let Ok(data) = rx.recv() {
+            let now = Instant::now();
+            let due = last_draw.map_or(true, |last| {
+                now.duration_since(last) >= Duration::from_millis(100)
+            });
+            if due {
+                last_draw = Some(now);
+                eprint!(
+                    "\r\x1b[K{} {}/{} dirs · {} found · {}",
+                    "[scanning]".cyan(),
+                    data.entries_checked,
+                    data.entries_to_check,
+                    data.found,
+                    data.current_stage.bright_black(),
+                );
+                let _ = std::io::stderr().flush();
+            }
+        }
+        // Clear the status line so it does not collide with the final summary.
+        eprint!("\r\x1b[K");
+        let _ = std::io::stderr().flush();
+    })
+}
 
-    // Start scanning
-    let stats = CleanStats::new();
-    scan_and_clean(&scan_path, &args, &stats)?;
+fn main() -> Result<()> {
+    let args = Args::parse();
 
-    // Display statistics
-    println!();
-    println!("{}", "=".repeat(50).bright_black());
-    println!("{}", "Cleanup Statistics:".bold().cyan());
-    println!(
-        "  {} {}",
-        "Found .DS_Store files:".bold(),
-        stats.get_found().to_string().yellow()
-    );
+    let output = Output::new(args.format, args.dry_run, args.verbose);
 
-    if !args.dry_run {
+    // Validate input path. Following the Mercurial dirstate fix, an
+    // inaccessible root degrades gracefully rather than panicking: report it,
+    // count it in the statistics so the JSON/human summary still renders, and
+    // exit non-zero.
+    let scan_path = match args.path.canonicalize() {
+        Ok(path) => path,
+        Err(e) => {
+            output.warn(format!(
+                "Cannot access the specified path {}: {}",
+                args.path.display(),
+                e
+            ));
+            let stats = CleanStats::new();
+            stats.increment_unreadable();
+            output.summary(&stats);
+            std::process::exit(1);
+        }
+    };
+
+    if !scan_path.is_dir() {
+        output.warn(format!("Path is not a directory: {}", scan_path.display()));
+        let stats = CleanStats::new();
+        stats.increment_unreadable();
+        output.summary(&stats);
+        std::process::exit(1);
+    }
+
+    // Display scan information (human mode only; the JSON modes keep stdout as
+    // pure structured data).
+    if output.is_human() {
         println!(
-            "  {} {}",
-            "Successfully moved to trash:".bold(),
-            stats.get_moved().to_string().green()
+            "{} {}",
+            "Scan path:".bold().cyan(),
+            scan_path.display().to_string().yellow()
         );
-        if stats.get_failed() > 0 {
+
+        if args.dry_run {
             println!(
-                "  {} {}",
-                "Failed:".bold(),
-                stats.get_failed().to_string().red()
+                "{}",
+                "Mode: Preview mode (files will not be removed)"
+                    .bold()
+                    .yellow()
             );
+        } else {
+            println!(
+                "{}",
+                "Mode: Execution mode (files will be moved to trash)"
+                    .bold()
+                    .green()
+            );
+        }
+
+        if args.no_recursive {
+            println!("{}", "Recursion: Disabled".bold());
+        } else if args.max_depth > 0 {
+            println!("{} {}", "Max depth:".bold(), args.max_depth);
         }
+
+        println!();
     }
-    println!("{}", "=".repeat(50).bright_black());
 
-    if args.dry_run && stats.get_found() > 0 {
+    // Compile the junk-file matcher from the enabled targets and custom globs.
+    let matcher = JunkMatcher::new(&args.targets, &args.patterns)?;
+
+    // Compile the exclude/include path filters.
+    let excluded = ExcludedItems::new(&args.exclude, &args.include)?;
+
+    // Start scanning
+    let stats = CleanStats::new();
+    scan_and_clean(&scan_path, &args, &matcher, &excluded, &output, &stats)?;
+
+    // Display statistics
+    output.summary(&stats);
+
+    if output.is_human() && args.dry_run && stats.get_found() > 0 {
         println!();
         println!(
             "{}",
@@ -167,119 +619,341 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn scan_and_clean(path: &Path, args: &Args, stats: &CleanStats) -> Result<()> {
-    let mut walker = WalkDir::new(path);
+/// A directory waiting to be scanned, tagged with its depth relative to the
+/// scan root (the root itself has depth 0).
+struct WorkItem {
+    path: PathBuf,
+    depth: usize,
+    /// Number of symlinks traversed to reach this directory along its branch.
+    symlink_jumps: usize,
+    /// Whether this directory still resolves inside the original scan root.
+    /// Files reached from a branch that escaped the root are never trashed.
+    within_root: bool,
+}
 
-    // Configure walker
-    if args.no_recursive {
-        walker = walker.max_depth(1);
+fn scan_and_clean(
+    path: &Path,
+    args: &Args,
+    matcher: &JunkMatcher,
+    excluded: &ExcludedItems,
+    output: &Output,
+    stats: &CleanStats,
+) -> Result<()> {
+    // The scan runs on a dedicated rayon thread pool so `--jobs` controls the
+    // parallelism independently of any ambient global pool. `jobs == 0` lets
+    // rayon pick `available_parallelism()`.
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs)
+        .build()
+        .context("Failed to build the worker thread pool")?;
+
+    // Wire up the optional progress reporter.
+    let (progress, reporter) = if args.progress {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        (Progress::enabled(tx), Some(spawn_reporter(rx)))
+    } else {
+        (Progress::disabled(), None)
+    };
+
+    let result =
+        pool.install(|| scan_worklist(path, args, matcher, excluded, output, stats, &progress));
+
+    // Drop the sender so the reporter's channel closes, then join it before the
+    // caller prints the final summary so the two outputs do not interleave.
+    drop(progress);
+    if let Some(reporter) = reporter {
+        let _ = reporter.join();
+    }
+
+    result
+}
+
+/// Recursive, rayon-backed worklist traversal.
+///
+/// Instead of a single serial walker we keep a queue of directories and, on
+/// every round, process the whole queue in parallel: each directory is read
+/// with `std::fs::read_dir`, its matching junk entries are handled in place, and
+/// its subdirectories are collected as the next round's work items. The shared
+/// `CleanStats` atomics make the per-file accounting safe across workers.
+fn scan_worklist(
+    root: &Path,
+    args: &Args,
+    matcher: &JunkMatcher,
+    excluded: &ExcludedItems,
+    output: &Output,
+    stats: &CleanStats,
+    progress: &Progress,
+) -> Result<()> {
+    // The maximum depth of the entries we look at: only directories below this
+    // depth are *read*, since files inside a directory at depth `d` live at
+    // depth `d + 1`, mirroring `walkdir`'s `max_depth` semantics.
+    let max_depth = if args.no_recursive {
+        Some(1)
     } else if args.max_depth > 0 {
-        walker = walker.max_depth(args.max_depth);
+        Some(args.max_depth)
+    } else {
+        None
+    };
+
+    let mut worklist = vec![WorkItem {
+        path: root.to_path_buf(),
+        depth: 0,
+        symlink_jumps: 0,
+        within_root: true,
+    }];
+
+    // The root counts toward the running total of directories to scan.
+    progress.add_to_check(worklist.len());
+
+    // Canonical directories already scheduled. Every enqueued path is already
+    // canonical (the root is canonicalized and normal children inherit that,
+    // while symlink targets are canonicalized when followed), so this dedups
+    // in-tree symlink aliasing without extra filesystem calls: a symlink target
+    // the normal walk also reaches is only scanned once.
+    let mut visited: std::collections::HashSet<PathBuf> =
+        worklist.iter().map(|w| w.path.clone()).collect();
+
+    while !worklist.is_empty() {
+        let next: Vec<WorkItem> = worklist
+            .par_iter()
+            .flat_map_iter(|item| {
+                process_directory(
+                    item, root, max_depth, args, matcher, excluded, output, stats, progress,
+                )
+            })
+            .collect();
+        // Drop any directory already scheduled before accounting for it.
+        let next: Vec<WorkItem> = next
+            .into_iter()
+            .filter(|w| visited.insert(w.path.clone()))
+            .collect();
+        progress.add_to_check(next.len());
+        worklist = next;
     }
 
-    // Iterate through directories
-    for entry in walker.into_iter().filter_entry(|e| {
-        // Filter conditions
-        if args.skip_hidden && e.depth() > 0 {
-            // Skip hidden directories (but not root directory and files)
-            if e.file_type().is_dir() {
-                if let Some(name) = e.file_name().to_str() {
-                    if name.starts_with('.') {
-                        return false;
-                    }
-                }
-            }
+    Ok(())
+}
+
+/// Scan a single directory: handle its matching junk entries and return the
+/// subdirectories that should be scanned next.
+fn process_directory(
+    item: &WorkItem,
+    root: &Path,
+    max_depth: Option<usize>,
+    args: &Args,
+    matcher: &JunkMatcher,
+    excluded: &ExcludedItems,
+    output: &Output,
+    stats: &CleanStats,
+    progress: &Progress,
+) -> Vec<WorkItem> {
+    // A directory at depth `d` is only worth reading when its files
+    // (at depth `d + 1`) are within the allowed depth. It was still counted in
+    // `to_check` when its parent enqueued it, so tick it before returning to
+    // keep the `checked/to_check` fraction converging.
+    if let Some(max) = max_depth {
+        if item.depth >= max {
+            progress.tick(&item.path, stats.get_found());
+            return Vec::new();
         }
-        true
-    }) {
-        match entry {
-            Ok(entry) => {
-                // Check if it's a file
-                if !entry.file_type().is_file() {
-                    continue;
-                }
+    }
 
-                // Strictly check if filename is .DS_Store
-                if !is_ds_store_file(&entry.path()) {
-                    continue;
-                }
+    let entries = match std::fs::read_dir(&item.path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            // Treat an unreadable directory (permissions, transient I/O) as
+            // empty: log it once, count it, and keep scanning siblings rather
+            // than dropping the whole subtree. Still tick it so progress stays
+            // consistent with the cumulative total.
+            stats.increment_unreadable();
+            output.warn(format!("Cannot read directory {}: {}", item.path.display(), e));
+            progress.tick(&item.path, stats.get_found());
+            return Vec::new();
+        }
+    };
 
-                stats.increment_found();
+    let mut subdirs = Vec::new();
 
-                let file_path = entry.path();
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                output.warn(e);
+                continue;
+            }
+        };
 
-                if args.verbose || args.dry_run {
-                    if args.dry_run {
-                        println!("{} {}", "[Preview]".bright_yellow(), file_path.display());
-                    } else {
-                        println!("{} {}", "[Found]".bright_blue(), file_path.display());
-                    }
-                }
+        let file_type = match entry.file_type() {
+            Ok(ft) => ft,
+            Err(e) => {
+                output.warn(e);
+                continue;
+            }
+        };
 
-                // If not in preview mode, move to trash
-                if !args.dry_run {
-                    match move_to_trash(file_path) {
-                        Ok(_) => {
-                            stats.increment_moved();
-                            if args.verbose {
-                                println!("  {} {}", "✓".green().bold(), "Moved to trash".green());
-                            }
-                        }
-                        Err(e) => {
-                            stats.increment_failed();
-                            eprintln!(
-                                "  {} Failed to move file: {}",
-                                "✗".red().bold(),
-                                e.to_string().red()
-                            );
-                        }
+        let path = entry.path();
+
+        if file_type.is_dir() {
+            // A directory that itself matches an enabled target (e.g.
+            // `.Spotlight-V100`, `.Trashes`) is trashed whole and not descended
+            // into. This is checked before the hidden-skip prune below so an
+            // explicitly-enabled dot-directory target isn't silently dropped
+            // under `--skip-hidden`.
+            if item.within_root && matcher.matches(&path) && excluded.allows_file(&path) {
+                handle_match(&path, args, matcher, output, stats);
+                continue;
+            }
+            // Skip hidden directories when requested (files are never skipped).
+            if args.skip_hidden {
+                if let Some(name) = entry.file_name().to_str() {
+                    if name.starts_with('.') {
+                        continue;
                     }
                 }
             }
-            Err(e) => {
-                eprintln!("{} {}", "Warning:".yellow(), e);
+            // Prune excluded subtrees before descent rather than per-file.
+            if excluded.is_excluded(&path) {
+                continue;
+            }
+            subdirs.push(WorkItem {
+                path,
+                depth: item.depth + 1,
+                symlink_jumps: item.symlink_jumps,
+                within_root: item.within_root,
+            });
+        } else if file_type.is_file() {
+            // Files reached from a branch that escaped the scan root are never
+            // trashed, even if their name matches an enabled target.
+            if item.within_root && matcher.matches(&path) && excluded.allows_file(&path) {
+                handle_match(&path, args, matcher, output, stats);
+            }
+        } else if file_type.is_symlink() && args.follow_symlinks {
+            if let Some(work) =
+                follow_symlink(&path, item, root, args, matcher, excluded, output, stats)
+            {
+                subdirs.push(work);
             }
         }
     }
 
-    Ok(())
+    progress.tick(&item.path, stats.get_found());
+
+    subdirs
 }
 
-/// Strictly check if the filename is .DS_Store
+/// Resolve a symlink encountered while `--follow-symlinks` is active.
 ///
-/// This function ensures we only process genuine .DS_Store files,
-/// avoiding accidental deletion of other files
-fn is_ds_store_file(path: &Path) -> bool {
-    if let Some(file_name) = path.file_name() {
-        if let Some(name_str) = file_name.to_str() {
-            // Strict filename matching
-            return name_str == ".DS_Store";
+/// A dangling link is warned about as a `NonExistentFile` and dropped. A link
+/// that would exceed `MAX_NUMBER_OF_SYMLINK_JUMPS` along its branch is warned
+/// about as `InfiniteRecursion` and dropped. A link to a directory returns the
+/// work item to enqueue (with the jump counter advanced and `within_root`
+/// narrowed once the target leaves the scan root); a link to a matching file is
+/// trashed in place only when it still resolves inside the root.
+fn follow_symlink(
+    path: &Path,
+    item: &WorkItem,
+    root: &Path,
+    args: &Args,
+    matcher: &JunkMatcher,
+    excluded: &ExcludedItems,
+    output: &Output,
+    stats: &CleanStats,
+) -> Option<WorkItem> {
+    // Canonicalizing both resolves the link and tells us whether it dangles.
+    let target = match std::fs::canonicalize(path) {
+        Ok(target) => target,
+        Err(_) => {
+            output.warn(format!("NonExistentFile: dangling symlink {}", path.display()));
+            return None;
+        }
+    };
+
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => {
+            output.warn(format!("NonExistentFile: dangling symlink {}", path.display()));
+            return None;
+        }
+    };
+
+    let within_root = item.within_root && target.starts_with(root);
+
+    if metadata.is_dir() {
+        if excluded.is_excluded(path) {
+            return None;
+        }
+        if item.symlink_jumps + 1 > MAX_NUMBER_OF_SYMLINK_JUMPS {
+            output.warn(format!(
+                "InfiniteRecursion: too many symlink jumps at {}",
+                path.display()
+            ));
+            return None;
+        }
+        Some(WorkItem {
+            path: target,
+            depth: item.depth + 1,
+            symlink_jumps: item.symlink_jumps + 1,
+            within_root,
+        })
+    } else if metadata.is_file() {
+        // Never trash a file that resolves outside the original scan root.
+        if within_root && matcher.matches(path) && excluded.allows_file(path) {
+            handle_match(path, args, matcher, output, stats);
+        }
+        None
+    } else {
+        None
+    }
+}
+
+/// Handle a confirmed junk match (a file, or a directory target such as
+/// `.Trashes`): report it and, unless in preview mode, move it to the trash.
+fn handle_match(
+    file_path: &Path,
+    args: &Args,
+    matcher: &JunkMatcher,
+    output: &Output,
+    stats: &CleanStats,
+) {
+    stats.increment_found();
+    output.found(file_path);
+
+    // If not in preview mode, move to trash
+    if !args.dry_run {
+        match move_to_trash(file_path, matcher) {
+            Ok(_) => {
+                stats.increment_moved();
+                output.moved(file_path);
+            }
+            Err(e) => {
+                stats.increment_failed();
+                output.failed(file_path, &e);
+            }
         }
     }
-    false
 }
 
 /// Safely move files to the system trash
 ///
 /// Uses the trash crate to ensure cross-platform compatibility
-fn move_to_trash(path: &Path) -> Result<()> {
-    // Verify filename again (double safety check)
-    if !is_ds_store_file(path) {
+fn move_to_trash(path: &Path, matcher: &JunkMatcher) -> Result<()> {
+    // Verify the file still matches an enabled pattern (double safety check)
+    if !matcher.matches(path) {
         anyhow::bail!(
-            "Safety check failed: filename is not .DS_Store: {}",
+            "Safety check failed: filename is not an enabled junk target: {}",
             path.display()
         );
     }
 
-    // Confirm file exists
+    // Confirm the path still exists
     if !path.exists() {
-        anyhow::bail!("File does not exist: {}", path.display());
+        anyhow::bail!("Path does not exist: {}", path.display());
     }
 
-    // Confirm it's a file and not a directory
-    if !path.is_file() {
-        anyhow::bail!("Not a file: {}", path.display());
-    }
+    // Both file- and directory-shaped targets are accepted here: some enabled
+    // targets (`.Spotlight-V100`, `.Trashes`) are directories, and the `trash`
+    // crate removes directories just as safely. The matcher check above remains
+    // the real safety gate.
 
     // Move to trash
     trash::delete(path).context(format!("Failed to move to trash: {}", path.display()))?;
@@ -292,12 +966,84 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_is_ds_store_file() {
-        assert!(is_ds_store_file(Path::new("/some/path/.DS_Store")));
-        assert!(is_ds_store_file(Path::new(".DS_Store")));
-        assert!(!is_ds_store_file(Path::new("/some/path/DS_Store")));
-        assert!(!is_ds_store_file(Path::new("/some/path/.DS_Store.txt")));
-        assert!(!is_ds_store_file(Path::new("/some/path/file.txt")));
-        assert!(!is_ds_store_file(Path::new("/some/path/.DS_Store2")));
+    fn test_ds_store_matcher() {
+        let matcher = JunkMatcher::new(&[Target::DsStore], &[]).unwrap();
+        assert!(matcher.matches(Path::new("/some/path/.DS_Store")));
+        assert!(matcher.matches(Path::new(".DS_Store")));
+        assert!(!matcher.matches(Path::new("/some/path/DS_Store")));
+        assert!(!matcher.matches(Path::new("/some/path/.DS_Store.txt")));
+        assert!(!matcher.matches(Path::new("/some/path/file.txt")));
+        assert!(!matcher.matches(Path::new("/some/path/.DS_Store2")));
+    }
+
+    #[test]
+    fn test_additional_targets() {
+        let matcher = JunkMatcher::new(
+            &[Target::DsStore, Target::AppleDouble, Target::ThumbsDb],
+            &[],
+        )
+        .unwrap();
+        assert!(matcher.matches(Path::new("/a/._resource")));
+        assert!(matcher.matches(Path::new("/a/Thumbs.db")));
+        assert!(matcher.matches(Path::new("/a/.DS_Store")));
+        // Desktop.ini is not enabled here.
+        assert!(!matcher.matches(Path::new("/a/Desktop.ini")));
+    }
+
+    #[test]
+    fn test_directory_targets() {
+        let matcher =
+            JunkMatcher::new(&[Target::SpotlightV100, Target::Trashes], &[]).unwrap();
+        assert!(matcher.matches(Path::new("/vol/.Spotlight-V100")));
+        assert!(matcher.matches(Path::new("/vol/.Trashes")));
+        assert!(!matcher.matches(Path::new("/vol/.DS_Store")));
+    }
+
+    #[test]
+    fn test_custom_glob_pattern() {
+        let matcher = JunkMatcher::new(&[], &["*.tmp".to_string()]).unwrap();
+        assert!(matcher.matches(Path::new("/a/scratch.tmp")));
+        assert!(!matcher.matches(Path::new("/a/scratch.txt")));
+    }
+
+    #[test]
+    fn test_excluded_items() {
+        let excluded =
+            ExcludedItems::new(&["**/node_modules/**".to_string()], &[]).unwrap();
+        assert!(excluded.is_excluded(Path::new("/proj/node_modules/pkg")));
+        assert!(!excluded.is_excluded(Path::new("/proj/src")));
+        assert!(!excluded.allows_file(Path::new("/proj/node_modules/pkg/.DS_Store")));
+        assert!(excluded.allows_file(Path::new("/proj/src/.DS_Store")));
+    }
+
+    #[test]
+    fn test_include_restricts() {
+        let excluded = ExcludedItems::new(&[], &["/proj/keep/**".to_string()]).unwrap();
+        assert!(excluded.allows_file(Path::new("/proj/keep/a/.DS_Store")));
+        assert!(!excluded.allows_file(Path::new("/proj/other/.DS_Store")));
+    }
+
+    #[test]
+    fn test_record_serialization() {
+        let moved = Record {
+            action: "moved",
+            path: "/a/.DS_Store".to_string(),
+            error: None,
+        };
+        // A successful record omits the null error field.
+        assert_eq!(
+            serde_json::to_string(&moved).unwrap(),
+            r#"{"action":"moved","path":"/a/.DS_Store"}"#
+        );
+
+        let failed = Record {
+            action: "failed",
+            path: "/a/.DS_Store".to_string(),
+            error: Some("permission denied".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_string(&failed).unwrap(),
+            r#"{"action":"failed","path":"/a/.DS_Store","error":"permission denied"}"#
+        );
     }
 }